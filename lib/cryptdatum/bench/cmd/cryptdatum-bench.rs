@@ -4,7 +4,7 @@ use std::fs::File;
 use std::io::Read;
 use std::process::exit;
 
-use cryptdatum::verify_header;
+use cryptdatum::{decode_header, has_valid_header, verify_payload, HEADER_SIZE};
 
 fn main() -> Result<(), Box<dyn Error>> {
   let args: Vec<String> = env::args().collect();
@@ -30,11 +30,18 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 fn cmd_verify(file: &str) -> Result<(), Box<dyn Error>> {
   let mut ctd = File::open(file)?;
-  let mut headb = [0; cryptdatum::HEADER_SIZE];
+  let mut data = Vec::new();
+  ctd.read_to_end(&mut data)?;
 
-  ctd.read_exact(&mut headb)?;
+  if !has_valid_header(&data) {
+      exit(1);
+  }
+
+  let header = decode_header(&mut &data[..])?;
+  let payload_end = (header.size as usize).min(data.len()).max(HEADER_SIZE);
+  let payload = &data[HEADER_SIZE..payload_end];
 
-  if !verify_header(&headb) {
+  if !verify_payload(&header, payload) {
       exit(1);
   }
 
@@ -23,6 +23,8 @@ fn main() -> Result<()> {
       "file-has-valid-header" => cmd_file_has_valid_header(filepath)?,
       "file-info" => cmd_file_info(filepath)?,
       // "file-info" => cmd_file_info(filepath)?,
+      "armor" => cmd_armor(filepath)?,
+      "file-verify" => cmd_file_verify(filepath)?,
       _ => {
           println!("invalid command");
           exit(1);
@@ -65,6 +67,35 @@ fn cmd_file_info(filepath: &str) -> Result<()> {
   Ok(())
 }
 
+fn cmd_armor(filepath: &str) -> Result<()> {
+  let mut ctd = File::open(filepath)?;
+  let header = decode_header(&mut ctd)?;
+  let stdout = std::io::stdout();
+  let mut handle = stdout.lock();
+  header.write_text(&mut handle, &[])?;
+  Ok(())
+}
+
+fn cmd_file_verify(filepath: &str) -> Result<()> {
+  let mut ctd = File::open(filepath)?;
+  let mut data = Vec::new();
+  ctd.read_to_end(&mut data)?;
+
+  if !has_valid_header(&data) {
+      exit(1);
+  }
+
+  let header = decode_header(&mut &data[..])?;
+  let payload_end = (header.size as usize).min(data.len()).max(cryptdatum::HEADER_SIZE);
+  let payload = &data[cryptdatum::HEADER_SIZE..payload_end];
+
+  if !verify_payload(&header, payload) {
+      exit(1);
+  }
+
+  Ok(())
+}
+
 fn pretty_size(size: u64) -> String {
   let units = ["B", "KB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
   let mut i = 0;
@@ -98,9 +129,9 @@ fn print_header(header: Header) {
   println!("| OPC          | 4          | Operation Counter           | uint32            | {:<31} |", header.opc);
   println!("| Checksum     | 8          | Checksum                    | uint64            | {:<31} |", header.checksum);
   println!("| Size         | 8          | Total size                  | uint64            | {:<31} |", header.size);
-  println!("| Comp. Alg.   | 2          | Compression algorithm       | uint16            | {:<31} |", header.compression_alg);
-  println!("| Encrypt. Alg | 2          | Encryption algorithm        | uint16            | {:<31} |", header.encryption_alg);
-  println!("| Sign. Type   | 2          | Signature type              | uint16            | {:<31} |", header.signature_type);
+  println!("| Comp. Alg.   | 2          | Compression algorithm       | uint16            | {:<31} |", header.compression_alg.name());
+  println!("| Encrypt. Alg | 2          | Encryption algorithm        | uint16            | {:<31} |", header.encryption_alg.name());
+  println!("| Sign. Type   | 2          | Signature type              | uint16            | {:<31} |", header.signature_type.name());
   println!("| Sign. Size   | 4          | Signature size              | uint32            | {:<31} |", header.signature_size);
   println!("| File Ext.    | 8          | File extension              | char[8]           | {:<31} |", header.file_ext);
   println!("| Custom       | 8          | Custom                      | uint8[8]          | {:03} {:03} {:03} {:03} {:03} {:03} {:03} {:03} |",
@@ -3,7 +3,7 @@
 // See the LICENSE file.
 
 use std::ops::BitAnd;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::convert::TryInto;
 
 /// Current version of the Cryptdatum format
@@ -60,9 +60,9 @@ pub struct Header {
   pub opc: u32, // Unique operation ID
   pub checksum: u64, // CRC64 checksum
   pub size: u64, // Total size of the data, incl. header and optional signature
-  pub compression_alg: u16, // compression algorithm
-  pub encryption_alg: u16, // encryption algorithm
-  pub signature_type: u16, // signature type
+  pub compression_alg: CompressionAlgorithm, // compression algorithm
+  pub encryption_alg: EncryptionAlgorithm, // encryption algorithm
+  pub signature_type: SignatureType, // signature type
   pub signature_size: u32, // signature size
   pub file_ext: String, // File extension
   pub custom: [u8; 8], // Custom field
@@ -84,6 +84,15 @@ pub enum DatumFlag {
   DatumStreamable = 1 << 9,
   DatumCustom = 1 << 10,
   DatumCompromised = 1 << 11,
+  // Datum carries a TLV metadata section, written immediately after the
+  // 80-byte (`HEADER_SIZE`) fixed header and before the payload that `size`
+  // accounts for. The section's length in bytes is carried in the
+  // `custom` field (reinterpreted as a little-endian u64 instead of an
+  // opaque tag) rather than a dedicated header field, since the fixed
+  // 80-byte layout has no spare bytes. Do not set `DatumCustom` at the
+  // same time: the two flags can't both claim `custom`. See
+  // `Header::tlv_section_len`.
+  DatumTlv = 1 << 12,
 }
 
 impl BitAnd<DatumFlag> for u64 {
@@ -109,27 +118,190 @@ impl From<u64> for DatumFlag {
       512 => DatumFlag::DatumStreamable,
       1024 => DatumFlag::DatumCustom,
       2048 => DatumFlag::DatumCompromised,
+      4096 => DatumFlag::DatumTlv,
       _ => todo!(),
     }
   }
 }
 
+/// Compression algorithm used for the payload.
+///
+/// Decoding an unrecognized discriminant yields `Unknown` rather than
+/// panicking, so callers can still round-trip a header they don't fully
+/// understand.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum CompressionAlgorithm {
+  #[default]
+  None,
+  Gzip,
+  Zstd,
+  Lz4,
+  Unknown(u16),
+}
+
+impl CompressionAlgorithm {
+  /// Human-readable name, used e.g. in the `file-info` table.
+  pub fn name(&self) -> &'static str {
+    match self {
+      CompressionAlgorithm::None => "None",
+      CompressionAlgorithm::Gzip => "Gzip",
+      CompressionAlgorithm::Zstd => "Zstd",
+      CompressionAlgorithm::Lz4 => "LZ4",
+      CompressionAlgorithm::Unknown(_) => "Unknown",
+    }
+  }
+}
+
+impl From<u16> for CompressionAlgorithm {
+  fn from(value: u16) -> Self {
+    match value {
+      0 => CompressionAlgorithm::None,
+      1 => CompressionAlgorithm::Gzip,
+      2 => CompressionAlgorithm::Zstd,
+      3 => CompressionAlgorithm::Lz4,
+      other => CompressionAlgorithm::Unknown(other),
+    }
+  }
+}
+
+impl From<CompressionAlgorithm> for u16 {
+  fn from(value: CompressionAlgorithm) -> Self {
+    match value {
+      CompressionAlgorithm::None => 0,
+      CompressionAlgorithm::Gzip => 1,
+      CompressionAlgorithm::Zstd => 2,
+      CompressionAlgorithm::Lz4 => 3,
+      CompressionAlgorithm::Unknown(v) => v,
+    }
+  }
+}
+
+/// Encryption algorithm used for the payload.
+///
+/// Decoding an unrecognized discriminant yields `Unknown` rather than
+/// panicking, so callers can still round-trip a header they don't fully
+/// understand.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum EncryptionAlgorithm {
+  #[default]
+  None,
+  AesGcm,
+  ChaCha20Poly1305,
+  Unknown(u16),
+}
+
+impl EncryptionAlgorithm {
+  /// Human-readable name, used e.g. in the `file-info` table.
+  pub fn name(&self) -> &'static str {
+    match self {
+      EncryptionAlgorithm::None => "None",
+      EncryptionAlgorithm::AesGcm => "AES-GCM",
+      EncryptionAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+      EncryptionAlgorithm::Unknown(_) => "Unknown",
+    }
+  }
+}
+
+impl From<u16> for EncryptionAlgorithm {
+  fn from(value: u16) -> Self {
+    match value {
+      0 => EncryptionAlgorithm::None,
+      1 => EncryptionAlgorithm::AesGcm,
+      2 => EncryptionAlgorithm::ChaCha20Poly1305,
+      other => EncryptionAlgorithm::Unknown(other),
+    }
+  }
+}
+
+impl From<EncryptionAlgorithm> for u16 {
+  fn from(value: EncryptionAlgorithm) -> Self {
+    match value {
+      EncryptionAlgorithm::None => 0,
+      EncryptionAlgorithm::AesGcm => 1,
+      EncryptionAlgorithm::ChaCha20Poly1305 => 2,
+      EncryptionAlgorithm::Unknown(v) => v,
+    }
+  }
+}
+
+/// Signature scheme used to sign the datum.
+///
+/// Decoding an unrecognized discriminant yields `Unknown` rather than
+/// panicking, so callers can still round-trip a header they don't fully
+/// understand.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SignatureType {
+  #[default]
+  None,
+  Ed25519,
+  Ecdsa,
+  Unknown(u16),
+}
+
+impl SignatureType {
+  /// Human-readable name, used e.g. in the `file-info` table.
+  pub fn name(&self) -> &'static str {
+    match self {
+      SignatureType::None => "None",
+      SignatureType::Ed25519 => "Ed25519",
+      SignatureType::Ecdsa => "ECDSA",
+      SignatureType::Unknown(_) => "Unknown",
+    }
+  }
+}
+
+impl From<u16> for SignatureType {
+  fn from(value: u16) -> Self {
+    match value {
+      0 => SignatureType::None,
+      1 => SignatureType::Ed25519,
+      2 => SignatureType::Ecdsa,
+      other => SignatureType::Unknown(other),
+    }
+  }
+}
+
+impl From<SignatureType> for u16 {
+  fn from(value: SignatureType) -> Self {
+    match value {
+      SignatureType::None => 0,
+      SignatureType::Ed25519 => 1,
+      SignatureType::Ecdsa => 2,
+      SignatureType::Unknown(v) => v,
+    }
+  }
+}
+
 #[derive(Debug)]
 pub enum ErrorType {
   Io(std::io::Error),
   Regular(ErrorKind),
-  Custom(String)
+  Custom(String),
+  /// The stream ended before a full 80-byte header could be read. Carries
+  /// the number of header bytes successfully read so far. This is a
+  /// resumable condition, not a hard I/O failure: on a socket or pipe the
+  /// caller should supply more bytes and retry rather than giving up.
+  Incomplete(usize),
+  /// The stream ended mid-payload, before `Header::size` bytes had been
+  /// delivered. Carries the number of payload bytes still undelivered
+  /// when EOF arrived, so this is distinguishable from a payload that was
+  /// fully and successfully read.
+  Truncated(u64),
 }
 
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum ErrorKind {
   IO,
+  InvalidMagic,
+  InvalidDelimiter,
 }
 
 impl ErrorKind {
   pub fn as_str(&self) -> &str {
     match *self {
-      ErrorKind::IO => "cryptdatum I/O error"
+      ErrorKind::IO => "cryptdatum I/O error",
+      ErrorKind::InvalidMagic => "cryptdatum invalid magic",
+      ErrorKind::InvalidDelimiter => "cryptdatum invalid delimiter",
     }
   }
 }
@@ -140,10 +312,22 @@ impl std::fmt::Display for ErrorType {
       ErrorType::Io(ref err) => err.fmt(f),
       ErrorType::Regular(ref err) => write!(f, "cryptdatum error: {:?}", err),
       ErrorType::Custom(ref err) => write!(f, "cryptdatum error: {:?}", err),
+      ErrorType::Incomplete(read) => write!(
+        f,
+        "cryptdatum error: incomplete header, read {} of {} bytes",
+        read, HEADER_SIZE
+      ),
+      ErrorType::Truncated(remaining) => write!(
+        f,
+        "cryptdatum error: stream ended with {} payload bytes undelivered",
+        remaining
+      ),
     }
   }
 }
 
+impl std::error::Error for ErrorType {}
+
 impl From<std::io::Error> for ErrorType {
   fn from(err: std::io::Error) -> ErrorType {
     ErrorType::Io(err)
@@ -240,15 +424,15 @@ pub fn has_valid_header(data: &[u8]) -> bool {
 
     // DatumCompressed compression algorithm must be set
     if flags & DatumFlag::DatumCompressed {
-      let algorithm = u16::from_le_bytes([data[46], data[47]]);
-      if algorithm < 1 {
+      let algorithm = CompressionAlgorithm::from(u16::from_le_bytes([data[46], data[47]]));
+      if algorithm == CompressionAlgorithm::None {
           return false;
       }
     }
     // DatumEncrypted encryption algorithm must be set
     if flags & DatumFlag::DatumEncrypted {
-      let algorithm = u16::from_le_bytes([data[48], data[49]]);
-      if algorithm < 1 {
+      let algorithm = EncryptionAlgorithm::from(u16::from_le_bytes([data[48], data[49]]));
+      if algorithm == EncryptionAlgorithm::None {
           return false;
       }
     }
@@ -262,8 +446,8 @@ pub fn has_valid_header(data: &[u8]) -> bool {
   // DatumSigned then Signature Type must be also set
   // however value of the signature Size may depend on Signature Type
   if flags & DatumFlag::DatumSigned {
-    let signature_type = u16::from_le_bytes([data[58], data[59]]);
-    if signature_type < 1 {
+    let signature_type = SignatureType::from(u16::from_le_bytes([data[50], data[51]]));
+    if signature_type == SignatureType::None {
       return false;
     }
   }
@@ -272,11 +456,38 @@ pub fn has_valid_header(data: &[u8]) -> bool {
   true
 }
 
+/// Decodes an 80-byte Cryptdatum header from `reader`.
+///
+/// Unlike a single `read` call, this loops until the full header is
+/// buffered or the stream ends, so it works correctly on sockets and pipes
+/// where `read` routinely returns short. `MAGIC` is validated as soon as
+/// its 8 bytes have arrived, so a non-Cryptdatum stream is rejected
+/// without waiting for the rest of the header. If the stream ends before
+/// `HEADER_SIZE` bytes are read, this returns `ErrorType::Incomplete`
+/// rather than a hard I/O failure, so callers on a live stream know to
+/// supply more bytes and retry.
 pub fn decode_header<R: Read>(reader: &mut R) -> Result<Header> {
   let mut header_buf = [0u8; HEADER_SIZE];
-  let bytes_read = reader.read(&mut header_buf)?;
-  if bytes_read < HEADER_SIZE {
-    return Err(ErrorType::Regular(ErrorKind::IO))
+  let mut filled = 0;
+  let mut magic_checked = false;
+
+  while filled < HEADER_SIZE {
+    let n = reader.read(&mut header_buf[filled..])?;
+    if n == 0 {
+      return Err(ErrorType::Incomplete(filled));
+    }
+    filled += n;
+
+    if !magic_checked && filled >= 8 {
+      if !header_buf[0..8].eq(&MAGIC) {
+        return Err(ErrorType::Regular(ErrorKind::InvalidMagic));
+      }
+      magic_checked = true;
+    }
+  }
+
+  if !header_buf[72..80].eq(&DELIMITER) {
+    return Err(ErrorType::Regular(ErrorKind::InvalidDelimiter));
   }
 
   let mut magic = [0; 8];
@@ -294,9 +505,9 @@ pub fn decode_header<R: Read>(reader: &mut R) -> Result<Header> {
     opc: u32::from_le_bytes(header_buf[26..30].try_into().unwrap()),
     checksum: u64::from_le_bytes(header_buf[30..38].try_into().unwrap()),
     size: u64::from_le_bytes(header_buf[38..46].try_into().unwrap()),
-    compression_alg: u16::from_le_bytes(header_buf[46..48].try_into().unwrap()),
-    encryption_alg: u16::from_le_bytes(header_buf[48..50].try_into().unwrap()),
-    signature_type: u16::from_le_bytes(header_buf[50..52].try_into().unwrap()),
+    compression_alg: CompressionAlgorithm::from(u16::from_le_bytes(header_buf[46..48].try_into().unwrap())),
+    encryption_alg: EncryptionAlgorithm::from(u16::from_le_bytes(header_buf[48..50].try_into().unwrap())),
+    signature_type: SignatureType::from(u16::from_le_bytes(header_buf[50..52].try_into().unwrap())),
     signature_size: u32::from_le_bytes(header_buf[52..56].try_into(). unwrap()),
     file_ext: std::str::from_utf8(&header_buf[56..64])?.to_owned(),
     custom: custom,
@@ -306,6 +517,588 @@ pub fn decode_header<R: Read>(reader: &mut R) -> Result<Header> {
   Ok(header)
 }
 
+/// Incremental, bounded-memory Cryptdatum decoder.
+///
+/// Unlike calling `decode_header` and then reading the rest of the data
+/// wholesale, `Decoder` streams the payload in caller-sized chunks, so
+/// callers can process arbitrarily large data with constant memory. This
+/// is the supported way to honor `DatumFlag::DatumStreamable`: check
+/// `is_streamable` after `decode_header` to decide whether the datum
+/// expects to be read this way.
+pub struct Decoder<R: Read> {
+  reader: R,
+  header: Option<Header>,
+  remaining: u64,
+}
+
+impl<R: Read> Decoder<R> {
+  /// Wraps `reader`. Call `decode_header` before `next_chunk`.
+  pub fn new(reader: R) -> Self {
+    Decoder {
+      reader,
+      header: None,
+      remaining: 0,
+    }
+  }
+
+  /// Reads and buffers the 80-byte header, tolerating short reads exactly
+  /// like the free function `decode_header`.
+  ///
+  /// Returns `ErrorType::Incomplete` if the stream ends before a full
+  /// header is read; the caller may retry once more bytes are available.
+  ///
+  /// If the header declares a TLV section (`Header::tlv_section_len`),
+  /// call `read_tlvs` before `next_chunk` to consume it; `next_chunk`
+  /// only ever streams the payload that follows it.
+  pub fn decode_header(&mut self) -> Result<&Header> {
+    let header = decode_header(&mut self.reader)?;
+    let tlv_len = header.tlv_section_len().unwrap_or(0);
+    self.remaining = header
+      .size
+      .saturating_sub(HEADER_SIZE as u64)
+      .saturating_sub(tlv_len);
+    self.header = Some(header);
+    Ok(self.header.as_ref().unwrap())
+  }
+
+  /// The header decoded by `decode_header`, if any.
+  pub fn header(&self) -> Option<&Header> {
+    self.header.as_ref()
+  }
+
+  /// Reads and decodes the TLV metadata section declared by the header,
+  /// using the length derived from `Header::tlv_section_len`.
+  ///
+  /// Must be called after `decode_header` and before `next_chunk`, since
+  /// the section is written between the header and the payload.  Returns
+  /// an empty `Vec` without reading anything if the header does not
+  /// declare a TLV section.
+  pub fn read_tlvs(&mut self) -> Result<Vec<Tlv>> {
+    let len = self
+      .header
+      .as_ref()
+      .and_then(|h| h.tlv_section_len())
+      .unwrap_or(0);
+    if len == 0 {
+      return Ok(Vec::new());
+    }
+    decode_tlvs(&mut self.reader, len as usize)
+  }
+
+  /// Whether the decoded datum declares `DatumFlag::DatumStreamable`.
+  ///
+  /// Returns `false` if `decode_header` has not been called yet.
+  pub fn is_streamable(&self) -> bool {
+    self.header.as_ref().is_some_and(|h| h.flags & DatumFlag::DatumStreamable)
+  }
+
+  /// Reads the next chunk of payload into `buf`.
+  ///
+  /// Reads at most `buf.len()` bytes, and never past the header's
+  /// declared `size`. Returns `Ok(0)` once the declared payload has been
+  /// fully read, the same end-of-stream convention as `Read::read`. If
+  /// the underlying reader hits EOF before `size` bytes have been
+  /// delivered, that is a truncated datum, not a successful end-of-stream,
+  /// so this returns `ErrorType::Truncated` instead of `Ok(0)`.
+  pub fn next_chunk(&mut self, buf: &mut [u8]) -> Result<usize> {
+    if self.remaining == 0 || buf.is_empty() {
+      return Ok(0);
+    }
+    let want = (buf.len() as u64).min(self.remaining) as usize;
+    let n = self.reader.read(&mut buf[..want])?;
+    if n == 0 {
+      return Err(ErrorType::Truncated(self.remaining));
+    }
+    self.remaining -= n as u64;
+    Ok(n)
+  }
+}
+
+/// Column width used to wrap ASCII-armored header text.
+///
+/// See [`Header::write_text`].
+const ARMOR_LINE_WIDTH: usize = 64;
+
+/// Delimiter line that terminates ASCII-armored header text.
+const ARMOR_DELIMITER: &str = "---";
+
+impl Header {
+  /// The length in bytes of the TLV metadata section written immediately
+  /// after this 80-byte header, if `DatumFlag::DatumTlv` is set.
+  ///
+  /// `custom` is reinterpreted as a little-endian `u64` length in this
+  /// case rather than an opaque caller value; see `DatumFlag::DatumTlv`.
+  /// Returns `None` if `DatumTlv` is not set, meaning there is no TLV
+  /// section to locate.
+  pub fn tlv_section_len(&self) -> Option<u64> {
+    if self.flags & DatumFlag::DatumTlv {
+      Some(u64::from_le_bytes(self.custom))
+    } else {
+      None
+    }
+  }
+
+  /// The byte range of the TLV metadata section within an encoded datum,
+  /// relative to the start of the datum (i.e. including the 80-byte
+  /// header), if `DatumFlag::DatumTlv` is set.
+  pub fn tlv_section_range(&self) -> Option<std::ops::Range<u64>> {
+    self
+      .tlv_section_len()
+      .map(|len| HEADER_SIZE as u64..HEADER_SIZE as u64 + len)
+  }
+
+  /// Writes an ASCII-armored text representation of this header (and, if
+  /// any, `tlvs`) to `out`, for copy-paste transport over text-only
+  /// channels.
+  ///
+  /// This mirrors the armored-key convention: the binary form (the 80
+  /// header bytes, followed by any TLV records) is base64-encoded, wrapped
+  /// at `ARMOR_LINE_WIDTH` characters per line, and terminated with a
+  /// `---` delimiter line. The output can be pasted into a bug report and
+  /// decoded losslessly back to bytes with `Header::read_text`.
+  ///
+  /// # Returns
+  ///
+  /// The number of bytes written.
+  pub fn write_text<W: Write>(&self, out: &mut W, tlvs: &[Tlv]) -> Result<usize> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE);
+    encode_header(&mut bytes, self)?;
+    encode_tlvs(&mut bytes, tlvs)?;
+
+    let encoded = base64::encode(&bytes);
+
+    let mut written = 0;
+    for chunk in encoded.as_bytes().chunks(ARMOR_LINE_WIDTH) {
+      out.write_all(chunk)?;
+      out.write_all(b"\n")?;
+      written += chunk.len() + 1;
+    }
+    out.write_all(ARMOR_DELIMITER.as_bytes())?;
+    out.write_all(b"\n")?;
+    written += ARMOR_DELIMITER.len() + 1;
+
+    Ok(written)
+  }
+
+  /// Reads an ASCII-armored header previously written by `write_text`.
+  ///
+  /// Strips line breaks, stops at the `---` delimiter line, base64-decodes
+  /// the remainder, and feeds the bytes through `decode_header`. Any TLV
+  /// records appended after the header are ignored; decode them
+  /// separately with `decode_tlvs` if needed.
+  pub fn read_text<R: Read>(input: &mut R) -> Result<Header> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    let mut encoded = String::with_capacity(text.len());
+    for line in text.lines() {
+      if line == ARMOR_DELIMITER {
+        break;
+      }
+      encoded.push_str(line);
+    }
+
+    let bytes = base64::decode(&encoded)?;
+    decode_header(&mut bytes.as_slice())
+  }
+}
+
+/// Minimal RFC 4648 base64 codec, used to ASCII-armor a header for
+/// copy-paste transport. The crate otherwise has no encoding dependencies,
+/// so this is implemented in-house rather than pulling one in.
+mod base64 {
+  const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+  pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+      let b0 = chunk[0];
+      let b1 = *chunk.get(1).unwrap_or(&0);
+      let b2 = *chunk.get(2).unwrap_or(&0);
+
+      out.push(ALPHABET[(b0 >> 2) as usize] as char);
+      out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+      out.push(if chunk.len() > 1 {
+        ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+      } else {
+        '='
+      });
+      out.push(if chunk.len() > 2 {
+        ALPHABET[(b2 & 0x3f) as usize] as char
+      } else {
+        '='
+      });
+    }
+    out
+  }
+
+  pub fn decode(s: &str) -> crate::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    let mut buf: u32 = 0;
+    let mut bits = 0;
+
+    for b in s.bytes().filter(|&b| b != b'=') {
+      buf = (buf << 6) | decode_char(b)? as u32;
+      bits += 6;
+      if bits >= 8 {
+        bits -= 8;
+        out.push((buf >> bits) as u8);
+      }
+    }
+
+    Ok(out)
+  }
+
+  fn decode_char(b: u8) -> crate::Result<u8> {
+    match b {
+      b'A'..=b'Z' => Ok(b - b'A'),
+      b'a'..=b'z' => Ok(b - b'a' + 26),
+      b'0'..=b'9' => Ok(b - b'0' + 52),
+      b'+' => Ok(62),
+      b'/' => Ok(63),
+      _ => Err(crate::ErrorType::Custom(format!("invalid base64 character: {:#04x}", b))),
+    }
+  }
+}
+
+/// Encodes a Cryptdatum header into its 80-byte binary representation.
+///
+/// This is the inverse of `decode_header`: every public field of `header`
+/// is little-endian-serialized at the exact offsets `decode_header` reads
+/// them from, `MAGIC` and `DELIMITER` are stamped automatically, and
+/// `file_ext` is zero-padded into its 8-byte slot. A round-trip through
+/// `encode_header` then `decode_header` reproduces every public field.
+///
+/// # Errors
+///
+/// Returns `ErrorType::Custom` if `header.file_ext` is longer than 8
+/// bytes — it has no 8-byte slot to fit in, and silently truncating it
+/// would both lose data and risk cutting a multi-byte UTF-8 character in
+/// half, which would then fail to decode even though encoding succeeded.
+///
+/// # Returns
+///
+/// The number of bytes written, i.e. `HEADER_SIZE`.
+pub fn encode_header<W: Write>(w: &mut W, header: &Header) -> Result<usize> {
+  let mut buf = [0u8; HEADER_SIZE];
+
+  buf[0..8].copy_from_slice(&MAGIC);
+  buf[8..10].copy_from_slice(&header.version.to_le_bytes());
+  buf[10..18].copy_from_slice(&header.flags.to_le_bytes());
+  buf[18..26].copy_from_slice(&header.timestamp.to_le_bytes());
+  buf[26..30].copy_from_slice(&header.opc.to_le_bytes());
+  buf[30..38].copy_from_slice(&header.checksum.to_le_bytes());
+  buf[38..46].copy_from_slice(&header.size.to_le_bytes());
+  buf[46..48].copy_from_slice(&u16::from(header.compression_alg).to_le_bytes());
+  buf[48..50].copy_from_slice(&u16::from(header.encryption_alg).to_le_bytes());
+  buf[50..52].copy_from_slice(&u16::from(header.signature_type).to_le_bytes());
+  buf[52..56].copy_from_slice(&header.signature_size.to_le_bytes());
+
+  let ext_bytes = header.file_ext.as_bytes();
+  if ext_bytes.len() > 8 {
+    return Err(ErrorType::Custom("file_ext exceeds 8 bytes".to_string()));
+  }
+  buf[56..56 + ext_bytes.len()].copy_from_slice(ext_bytes);
+
+  buf[64..72].copy_from_slice(&header.custom);
+  buf[72..80].copy_from_slice(&DELIMITER);
+
+  w.write_all(&buf)?;
+  Ok(HEADER_SIZE)
+}
+
+/// Builder for constructing a [`Header`] ready for `encode_header`.
+///
+/// This mirrors the Creator/Reader split used by binary-packet crates:
+/// callers set only the fields they care about, and `build` stamps `MAGIC`
+/// and `DELIMITER` automatically rather than requiring the caller to know
+/// about them.
+#[derive(Default)]
+pub struct HeaderBuilder {
+  version: u16,
+  flags: u64,
+  timestamp: u64,
+  opc: u32,
+  checksum: u64,
+  size: u64,
+  compression_alg: CompressionAlgorithm,
+  encryption_alg: EncryptionAlgorithm,
+  signature_type: SignatureType,
+  signature_size: u32,
+  file_ext: String,
+  custom: [u8; 8],
+}
+
+impl HeaderBuilder {
+  pub fn new() -> Self {
+    HeaderBuilder::default()
+  }
+
+  pub fn version(mut self, version: u16) -> Self {
+    self.version = version;
+    self
+  }
+
+  pub fn flags(mut self, flags: u64) -> Self {
+    self.flags = flags;
+    self
+  }
+
+  pub fn timestamp(mut self, timestamp: u64) -> Self {
+    self.timestamp = timestamp;
+    self
+  }
+
+  pub fn opc(mut self, opc: u32) -> Self {
+    self.opc = opc;
+    self
+  }
+
+  pub fn checksum(mut self, checksum: u64) -> Self {
+    self.checksum = checksum;
+    self
+  }
+
+  pub fn size(mut self, size: u64) -> Self {
+    self.size = size;
+    self
+  }
+
+  pub fn compression_alg(mut self, compression_alg: CompressionAlgorithm) -> Self {
+    self.compression_alg = compression_alg;
+    self
+  }
+
+  pub fn encryption_alg(mut self, encryption_alg: EncryptionAlgorithm) -> Self {
+    self.encryption_alg = encryption_alg;
+    self
+  }
+
+  pub fn signature_type(mut self, signature_type: SignatureType) -> Self {
+    self.signature_type = signature_type;
+    self
+  }
+
+  pub fn signature_size(mut self, signature_size: u32) -> Self {
+    self.signature_size = signature_size;
+    self
+  }
+
+  pub fn file_ext(mut self, file_ext: &str) -> Self {
+    self.file_ext = file_ext.to_owned();
+    self
+  }
+
+  pub fn custom(mut self, custom: [u8; 8]) -> Self {
+    self.custom = custom;
+    self
+  }
+
+  /// Sets the length of the TLV metadata section that follows the
+  /// header, by stamping it into `custom` as a little-endian `u64`. The
+  /// caller must also set `DatumFlag::DatumTlv` (and must not also set
+  /// `DatumFlag::DatumCustom`) via `flags` for `Header::tlv_section_len`
+  /// to read it back.
+  pub fn tlv_section_len(mut self, len: u64) -> Self {
+    self.custom = len.to_le_bytes();
+    self
+  }
+
+  /// Builds the `Header`, stamping `MAGIC` and `DELIMITER`.
+  pub fn build(self) -> Header {
+    Header {
+      magic: MAGIC,
+      version: self.version,
+      flags: self.flags,
+      timestamp: self.timestamp,
+      opc: self.opc,
+      checksum: self.checksum,
+      size: self.size,
+      compression_alg: self.compression_alg,
+      encryption_alg: self.encryption_alg,
+      signature_type: self.signature_type,
+      signature_size: self.signature_size,
+      file_ext: self.file_ext,
+      custom: self.custom,
+      delimiter: DELIMITER,
+    }
+  }
+}
+
+/// Polynomial for the CRC64 checksum (CRC-64/ISO, reflected form).
+const CRC64_POLY: u64 = 0xD800000000000000;
+
+/// Builds the table-driven lookup table for the CRC64 checksum.
+const fn crc64_table() -> [u64; 256] {
+  let mut table = [0u64; 256];
+  let mut i = 0usize;
+  while i < 256 {
+    let mut crc = i as u64;
+    let mut j = 0;
+    while j < 8 {
+      if crc & 1 == 1 {
+        crc = (crc >> 1) ^ CRC64_POLY;
+      } else {
+        crc >>= 1;
+      }
+      j += 1;
+    }
+    table[i] = crc;
+    i += 1;
+  }
+  table
+}
+
+const CRC64_TABLE: [u64; 256] = crc64_table();
+
+/// Computes the CRC64 checksum of `data`.
+///
+/// This is a table-driven CRC-64/ISO checksum (polynomial
+/// `0xD800000000000000`, reflected input/output, init `0`). An empty slice
+/// checksums to `0`, the defined init value.
+pub fn checksum(data: &[u8]) -> u64 {
+  let mut crc: u64 = 0;
+  for &b in data {
+    let idx = ((crc ^ b as u64) & 0xff) as usize;
+    crc = CRC64_TABLE[idx] ^ (crc >> 8);
+  }
+  crc
+}
+
+/// Verifies the integrity of `payload` against `header.checksum`.
+///
+/// `payload` must be the data that follows the 80-byte header, up to
+/// `header.size`; the header itself is never covered by the checksum. If
+/// `DatumChecksum` is not set on `header.flags`, there is nothing to
+/// verify and this function returns `true`.
+pub fn verify_payload(header: &Header, payload: &[u8]) -> bool {
+  if !(header.flags & DatumFlag::DatumChecksum) {
+    return true;
+  }
+  checksum(payload) == header.checksum
+}
+
+/// A single type-length-value record in a TLV metadata section.
+///
+/// TLV records let a datum carry variable-length metadata that the fixed
+/// 8-byte `custom` header field is too small for. A section is a sequence
+/// of consecutive `[tag:u16][len:u16][value:len]` records, written
+/// immediately after the header when `DatumFlag::DatumTlv` is set.
+/// Unknown tags are preserved verbatim on decode so forward compatibility
+/// holds when re-encoding a section this library doesn't fully understand.
+///
+/// The section's length is not a free-standing `decode_tlvs` parameter
+/// in practice: it's carried on the header itself, via
+/// `Header::tlv_section_len` (which reads the length back out of
+/// `custom`). `Decoder::read_tlvs` uses it to read the section
+/// automatically between `decode_header` and the payload.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tlv {
+  tag: u16,
+  value: Vec<u8>,
+}
+
+impl Tlv {
+  /// Creates a new TLV record.
+  pub fn new(tag: u16, value: Vec<u8>) -> Self {
+    Tlv { tag, value }
+  }
+}
+
+/// Read-side accessors shared by TLV records.
+pub trait GenericTlv {
+  /// The record's tag.
+  fn tag(&self) -> u16;
+  /// The record's value bytes.
+  fn len_value(&self) -> &[u8];
+  /// The number of bytes this record occupies when written:
+  /// `2 (tag) + 2 (len) + value.len()`.
+  fn len_written(&self) -> usize;
+}
+
+impl GenericTlv for Tlv {
+  fn tag(&self) -> u16 {
+    self.tag
+  }
+
+  fn len_value(&self) -> &[u8] {
+    &self.value
+  }
+
+  fn len_written(&self) -> usize {
+    4 + self.value.len()
+  }
+}
+
+/// Write-side encoding for a TLV record.
+pub trait WritableTlv {
+  /// Writes the record as `[tag:u16][len:u16][value]` and returns the
+  /// number of bytes written.
+  fn write_to<W: Write>(&self, w: &mut W) -> Result<usize>;
+}
+
+impl WritableTlv for Tlv {
+  fn write_to<W: Write>(&self, w: &mut W) -> Result<usize> {
+    let len: u16 = self.value.len().try_into()
+      .map_err(|_| ErrorType::Custom("TLV value exceeds u16 length".to_string()))?;
+    w.write_all(&self.tag.to_le_bytes())?;
+    w.write_all(&len.to_le_bytes())?;
+    w.write_all(&self.value)?;
+    Ok(self.len_written())
+  }
+}
+
+/// Decodes consecutive `[tag:u16][len:u16][value:len]` TLV records from
+/// `reader`, consuming exactly `section_len` bytes.
+///
+/// `section_len` is normally `Header::tlv_section_len` for a header
+/// that sets `DatumFlag::DatumTlv`; see `Decoder::read_tlvs` for the
+/// common case of decoding straight off a `Header` without computing
+/// this by hand.
+///
+/// Rejects a declared value length that would overrun the section, and
+/// rejects a section that ends in the middle of a record header.
+pub fn decode_tlvs<R: Read>(reader: &mut R, section_len: usize) -> Result<Vec<Tlv>> {
+  let mut remaining = section_len;
+  let mut tlvs = Vec::new();
+
+  while remaining > 0 {
+    if remaining < 4 {
+      return Err(ErrorType::Custom("TLV section truncated before a record header".to_string()));
+    }
+    let mut head = [0u8; 4];
+    reader.read_exact(&mut head)?;
+    let tag = u16::from_le_bytes([head[0], head[1]]);
+    let len = u16::from_le_bytes([head[2], head[3]]) as usize;
+    remaining -= 4;
+
+    if len > remaining {
+      return Err(ErrorType::Custom("TLV value length overruns section".to_string()));
+    }
+
+    let mut value = vec![0u8; len];
+    reader.read_exact(&mut value)?;
+    remaining -= len;
+
+    tlvs.push(Tlv { tag, value });
+  }
+
+  Ok(tlvs)
+}
+
+/// Encodes `tlvs` to `w` as consecutive TLV records.
+///
+/// # Returns
+///
+/// The total number of bytes written.
+pub fn encode_tlvs<W: Write>(w: &mut W, tlvs: &[Tlv]) -> Result<usize> {
+  let mut written = 0;
+  for tlv in tlvs {
+    written += tlv.write_to(w)?;
+  }
+  Ok(written)
+}
+
 pub mod timestamp {
   //! The `timestamp` module provides functions for formatting and parsing
   //! UTC nanoseconds timestamps as strings.
@@ -334,16 +1127,16 @@ pub mod timestamp {
   /// let ts = 1234567890;
   /// let fmt = "%Y-%m-%dT%H:%M:%S%nZ";
   /// let s = format(fmt, ts);
-  /// assert_eq!(s, "1970-01-01T01:00:00.234567890Z");
+  /// assert_eq!(s, "1970-01-01T00:00:01.234567890Z");
   /// ```
   pub fn format(fmt: &str, ts: u64) -> String {
     let (secs, nsec) = div_rem(ts, 1_000_000_000);
     let days: u64 = secs / 86400;
     let (year, month, day) = get_date(days);
 
-    let hour: u8 = (secs % 60) as u8;
+    let hour: u8 = ((secs / 3600) % 24) as u8;
     let min: u8 = ((secs / 60) % 60) as u8;
-    let sec: u8 = ((secs / 3600) % 24) as u8;
+    let sec: u8 = (secs % 60) as u8;
 
     let mut buf = [0; MAX_BUF_SIZE];
     let mut i = 0; // layout cursor
@@ -390,6 +1183,131 @@ pub mod timestamp {
     res
   }
 
+  /// Parses a UTC nanoseconds timestamp from an RFC 3339 `date-time` string.
+  ///
+  /// Accepts strings such as `2022-05-10T04:03:02.000000001Z` as well as
+  /// fixed timezone offsets like `2022-05-10T04:03:02+02:00` or
+  /// `2022-05-10T04:03:02-0500`. The fractional seconds component
+  /// (`secfrac`) is optional and may be 1-9 digits; it is right-padded with
+  /// zeros to nanosecond precision. This is the inverse of `format` with
+  /// the `"%Y-%m-%dT%H:%M:%S%nZ"` layout: `parse(format(ts)) == ts`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use cryptdatum::timestamp::parse;
+  ///
+  /// assert_eq!(parse("2022-05-10T04:03:02.000000001Z").unwrap(), 1652155382000000001);
+  /// ```
+  pub fn parse(s: &str) -> crate::Result<u64> {
+    let bytes = s.as_bytes();
+    if bytes.len() < 19 {
+      return Err(parse_err("timestamp too short"));
+    }
+
+    let year = parse_num(s, 0, 4)?;
+    expect_byte(bytes, 4, b'-')?;
+    let month = parse_num(s, 5, 2)? as u8;
+    expect_byte(bytes, 7, b'-')?;
+    let day = parse_num(s, 8, 2)? as u8;
+    expect_byte(bytes, 10, b'T')?;
+    let hour = parse_num(s, 11, 2)? as u8;
+    expect_byte(bytes, 13, b':')?;
+    let minute = parse_num(s, 14, 2)? as u8;
+    expect_byte(bytes, 16, b':')?;
+    let second = parse_num(s, 17, 2)? as u8;
+
+    if year < 1970 {
+      return Err(parse_err("timestamp before Unix epoch"));
+    }
+    if !(1..=12).contains(&month) {
+      return Err(parse_err("month out of range"));
+    }
+    if day < 1 || day as u64 > days_in_month(year, month) {
+      return Err(parse_err("day out of range"));
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+      return Err(parse_err("time field out of range"));
+    }
+
+    let mut i = 19;
+    let mut nanos: u64 = 0;
+    if i < bytes.len() && bytes[i] == b'.' {
+      i += 1;
+      let start = i;
+      while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+      }
+      let digits = i - start;
+      if digits == 0 || digits > 9 {
+        return Err(parse_err("invalid fractional seconds"));
+      }
+      let mut frac: u64 = s[start..i].parse().map_err(|_| parse_err("invalid fractional seconds"))?;
+      for _ in digits..9 {
+        frac *= 10;
+      }
+      nanos = frac;
+    }
+
+    if i >= bytes.len() {
+      return Err(parse_err("missing timezone offset"));
+    }
+    let offset_secs: i64 = match bytes[i] {
+      b'Z' | b'z' => {
+        i += 1;
+        0
+      },
+      b'+' | b'-' => {
+        let sign: i64 = if bytes[i] == b'-' { -1 } else { 1 };
+        i += 1;
+        let offset_hour = parse_num(s, i, 2)? as i64;
+        i += 2;
+        if i < bytes.len() && bytes[i] == b':' {
+          i += 1;
+        }
+        let offset_minute = parse_num(s, i, 2)? as i64;
+        i += 2;
+        sign * (offset_hour * 3600 + offset_minute * 60)
+      },
+      _ => return Err(parse_err("invalid timezone offset")),
+    };
+
+    if i != bytes.len() {
+      return Err(parse_err("trailing characters in timestamp"));
+    }
+
+    let days = days_from_date(year, month, day);
+    let local_secs = days as i64 * 86400 + hour as i64 * 3600 + minute as i64 * 60 + second as i64;
+    let secs = local_secs - offset_secs;
+    if secs < 0 {
+      return Err(parse_err("timestamp before Unix epoch"));
+    }
+
+    Ok(secs as u64 * 1_000_000_000 + nanos)
+  }
+
+  /// Builds a `cryptdatum::ErrorType::Custom` for an invalid timestamp string.
+  fn parse_err(msg: &str) -> crate::ErrorType {
+    crate::ErrorType::Custom(msg.to_string())
+  }
+
+  /// Parses `len` ASCII digits starting at `start` in `s` as a `u64`.
+  fn parse_num(s: &str, start: usize, len: usize) -> crate::Result<u64> {
+    let bytes = s.as_bytes();
+    if start + len > bytes.len() || !bytes[start..start + len].iter().all(u8::is_ascii_digit) {
+      return Err(parse_err("invalid number in timestamp"));
+    }
+    Ok(s[start..start + len].parse().unwrap())
+  }
+
+  /// Checks that `bytes[idx]` equals `expected`.
+  fn expect_byte(bytes: &[u8], idx: usize, expected: u8) -> crate::Result<()> {
+    if idx >= bytes.len() || bytes[idx] != expected {
+      return Err(parse_err("unexpected character in timestamp"));
+    }
+    Ok(())
+  }
+
   /// Writes the given string to the given byte array starting at the given index.
   ///
   /// # Returns
@@ -467,6 +1385,19 @@ pub mod timestamp {
       _ => panic!("Invalid month: {}", month),
     }
   }
+
+  /// Calculates the number of days since the Unix epoch for the given
+  /// year, month, and day. This is the inverse of `get_date`.
+  fn days_from_date(year: u64, month: u8, day: u8) -> u64 {
+    let mut days: u64 = 0;
+    for y in 1970..year {
+      days += if is_leap_year(y) { 366 } else { 365 };
+    }
+    for m in 1..month {
+      days += days_in_month(year, m);
+    }
+    days + (day - 1) as u64
+  }
 }
 
 #[cfg(test)]
@@ -531,4 +1462,322 @@ mod tests {
     data[72] = 0x00;
     assert!(!has_valid_header(&data));
   }
+
+  #[test]
+  fn encode_decode_header_roundtrip() {
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .flags(DatumFlag::DatumOPC as u64)
+      .timestamp(MAGIC_DATE)
+      .opc(7)
+      .checksum(42)
+      .size(1024)
+      .compression_alg(CompressionAlgorithm::Gzip)
+      .encryption_alg(EncryptionAlgorithm::ChaCha20Poly1305)
+      .signature_type(SignatureType::Ed25519)
+      .signature_size(64)
+      .file_ext("txt")
+      .custom([1, 2, 3, 4, 5, 6, 7, 8])
+      .build();
+
+    let mut buf = Vec::new();
+    let written = encode_header(&mut buf, &header).unwrap();
+    assert_eq!(written, HEADER_SIZE);
+    assert_eq!(buf.len(), HEADER_SIZE);
+
+    let decoded = decode_header(&mut buf.as_slice()).unwrap();
+    assert_eq!(decoded.version, header.version);
+    assert_eq!(decoded.flags, header.flags);
+    assert_eq!(decoded.timestamp, header.timestamp);
+    assert_eq!(decoded.opc, header.opc);
+    assert_eq!(decoded.checksum, header.checksum);
+    assert_eq!(decoded.size, header.size);
+    assert_eq!(decoded.compression_alg, header.compression_alg);
+    assert_eq!(decoded.encryption_alg, header.encryption_alg);
+    assert_eq!(decoded.signature_type, header.signature_type);
+    assert_eq!(decoded.signature_size, header.signature_size);
+    assert_eq!(decoded.file_ext.trim_end_matches('\0'), "txt");
+    assert_eq!(decoded.custom, header.custom);
+  }
+
+  #[test]
+  fn encode_header_rejects_oversized_file_ext() {
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .file_ext("toolongext")
+      .build();
+
+    let mut buf = Vec::new();
+    match encode_header(&mut buf, &header) {
+      Err(ErrorType::Custom(_)) => {}
+      other => panic!("expected a Custom error, got {:?}", other),
+    }
+  }
+
+  #[test]
+  fn algorithm_enums_roundtrip_through_u16() {
+    assert_eq!(CompressionAlgorithm::from(2u16), CompressionAlgorithm::Zstd);
+    assert_eq!(u16::from(CompressionAlgorithm::Zstd), 2);
+    assert_eq!(EncryptionAlgorithm::from(1u16), EncryptionAlgorithm::AesGcm);
+    assert_eq!(u16::from(EncryptionAlgorithm::AesGcm), 1);
+    assert_eq!(SignatureType::from(2u16), SignatureType::Ecdsa);
+    assert_eq!(u16::from(SignatureType::Ecdsa), 2);
+  }
+
+  #[test]
+  fn algorithm_enums_map_unknown_discriminants() {
+    assert_eq!(CompressionAlgorithm::from(99u16), CompressionAlgorithm::Unknown(99));
+    assert_eq!(u16::from(CompressionAlgorithm::Unknown(99)), 99);
+    assert_eq!(CompressionAlgorithm::from(99u16).name(), "Unknown");
+  }
+
+  #[test]
+  fn checksum_empty_payload_is_init_value() {
+    assert_eq!(checksum(&[]), 0);
+  }
+
+  #[test]
+  fn verify_payload_detects_tampering() {
+    let payload = b"cryptdatum payload";
+    let mut header = HeaderBuilder::new()
+      .flags(DatumFlag::DatumChecksum as u64)
+      .checksum(checksum(payload))
+      .build();
+    assert!(verify_payload(&header, payload));
+
+    header.checksum ^= 1;
+    assert!(!verify_payload(&header, payload));
+  }
+
+  #[test]
+  fn verify_payload_ignores_payload_without_checksum_flag() {
+    let header = HeaderBuilder::new().build();
+    assert!(verify_payload(&header, b"anything"));
+  }
+
+  #[test]
+  fn timestamp_parse_matches_magic_date() {
+    assert_eq!(timestamp::parse("2022-05-10T04:03:02.000000001Z").unwrap(), MAGIC_DATE);
+  }
+
+  #[test]
+  fn timestamp_parse_accepts_fixed_offsets() {
+    let plus = timestamp::parse("2022-05-10T06:03:02.000000001+02:00").unwrap();
+    let minus = timestamp::parse("2022-05-09T23:03:02.000000001-0500").unwrap();
+    assert_eq!(plus, MAGIC_DATE);
+    assert_eq!(minus, MAGIC_DATE);
+  }
+
+  #[test]
+  fn timestamp_parse_format_roundtrip() {
+    let ts = MAGIC_DATE;
+    let s = timestamp::format("%Y-%m-%dT%H:%M:%S%nZ", ts);
+    assert_eq!(timestamp::parse(&s).unwrap(), ts);
+  }
+
+  #[test]
+  fn timestamp_parse_rejects_out_of_range_fields() {
+    assert!(timestamp::parse("2022-13-10T04:03:02Z").is_err());
+    assert!(timestamp::parse("2022-02-30T04:03:02Z").is_err());
+    assert!(timestamp::parse("2022-05-10T04:03:02.1234567890Z").is_err());
+  }
+
+  #[test]
+  fn timestamp_parse_rejects_pre_epoch_years() {
+    assert!(timestamp::parse("1900-01-01T00:00:00Z").is_err());
+    assert!(timestamp::parse("1969-06-15T00:00:00Z").is_err());
+    assert!(timestamp::parse("1969-12-31T23:59:59Z").is_err());
+  }
+
+  #[test]
+  fn tlv_encode_decode_roundtrip() {
+    let tlvs = vec![
+      Tlv::new(1, b"hello".to_vec()),
+      Tlv::new(2, vec![]),
+      Tlv::new(0xBEEF, vec![9; 32]),
+    ];
+
+    let mut buf = Vec::new();
+    let written = encode_tlvs(&mut buf, &tlvs).unwrap();
+    assert_eq!(written, buf.len());
+
+    let decoded = decode_tlvs(&mut buf.as_slice(), buf.len()).unwrap();
+    assert_eq!(decoded, tlvs);
+  }
+
+  #[test]
+  fn header_locates_and_decoder_reads_tlv_section() {
+    let tlvs = vec![Tlv::new(1, b"hello".to_vec()), Tlv::new(2, vec![9; 4])];
+    let mut tlv_bytes = Vec::new();
+    encode_tlvs(&mut tlv_bytes, &tlvs).unwrap();
+
+    let payload = b"payload after the tlv section";
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .flags(DatumFlag::DatumTlv as u64)
+      .tlv_section_len(tlv_bytes.len() as u64)
+      .size((HEADER_SIZE + tlv_bytes.len() + payload.len()) as u64)
+      .build();
+
+    assert_eq!(header.tlv_section_len(), Some(tlv_bytes.len() as u64));
+    assert_eq!(
+      header.tlv_section_range(),
+      Some(HEADER_SIZE as u64..(HEADER_SIZE + tlv_bytes.len()) as u64)
+    );
+
+    let mut datum = Vec::new();
+    encode_header(&mut datum, &header).unwrap();
+    datum.extend_from_slice(&tlv_bytes);
+    datum.extend_from_slice(payload);
+
+    let mut decoder = Decoder::new(datum.as_slice());
+    decoder.decode_header().unwrap();
+    let decoded_tlvs = decoder.read_tlvs().unwrap();
+    assert_eq!(decoded_tlvs, tlvs);
+
+    let mut collected = Vec::new();
+    let mut chunk = [0u8; 8];
+    loop {
+      let n = decoder.next_chunk(&mut chunk).unwrap();
+      if n == 0 {
+        break;
+      }
+      collected.extend_from_slice(&chunk[..n]);
+    }
+    assert_eq!(collected, payload);
+  }
+
+  #[test]
+  fn tlv_rejects_overrunning_length() {
+    // tag=1, len=10, but only 2 bytes of value follow
+    let buf = [1, 0, 10, 0, 0xAA, 0xBB];
+    assert!(decode_tlvs(&mut &buf[..], buf.len()).is_err());
+  }
+
+  #[test]
+  fn tlv_rejects_truncated_record_header() {
+    let buf = [1, 0, 0];
+    assert!(decode_tlvs(&mut &buf[..], buf.len()).is_err());
+  }
+
+  #[test]
+  fn header_text_roundtrip() {
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .timestamp(MAGIC_DATE)
+      .size(1024)
+      .file_ext("bin")
+      .build();
+
+    let mut text = Vec::new();
+    header.write_text(&mut text, &[]).unwrap();
+    let armored = String::from_utf8(text).unwrap();
+    assert!(armored.ends_with("---\n"));
+
+    let decoded = Header::read_text(&mut armored.as_bytes()).unwrap();
+    assert_eq!(decoded.version, header.version);
+    assert_eq!(decoded.timestamp, header.timestamp);
+    assert_eq!(decoded.size, header.size);
+  }
+
+  #[test]
+  fn base64_roundtrip() {
+    let data = b"cryptdatum header bytes, 80 of them in practice";
+    let encoded = base64::encode(data);
+    let decoded = base64::decode(&encoded).unwrap();
+    assert_eq!(decoded, data);
+  }
+
+  /// A `Read` impl that yields at most one byte per call, to exercise
+  /// short-read handling the way a real socket or pipe would.
+  struct OneByteAtATime<'a>(&'a [u8]);
+
+  impl<'a> Read for OneByteAtATime<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+      if self.0.is_empty() || buf.is_empty() {
+        return Ok(0);
+      }
+      buf[0] = self.0[0];
+      self.0 = &self.0[1..];
+      Ok(1)
+    }
+  }
+
+  #[test]
+  fn decode_header_tolerates_short_reads() {
+    let header = HeaderBuilder::new().version(VERSION).size(100).build();
+    let mut buf = Vec::new();
+    encode_header(&mut buf, &header).unwrap();
+
+    let decoded = decode_header(&mut OneByteAtATime(&buf)).unwrap();
+    assert_eq!(decoded.version, header.version);
+  }
+
+  #[test]
+  fn decode_header_reports_incomplete_stream() {
+    let header = HeaderBuilder::new().version(VERSION).build();
+    let mut buf = Vec::new();
+    encode_header(&mut buf, &header).unwrap();
+
+    let truncated = &buf[..HEADER_SIZE - 10];
+    match decode_header(&mut OneByteAtATime(truncated)) {
+      Err(ErrorType::Incomplete(read)) => assert_eq!(read, HEADER_SIZE - 10),
+      Err(other) => panic!("unexpected error: {:?}", other),
+      Ok(_) => panic!("expected an Incomplete error"),
+    }
+  }
+
+  #[test]
+  fn decoder_streams_payload_in_chunks() {
+    let payload = b"the quick brown fox jumps over the lazy dog";
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .flags(DatumFlag::DatumStreamable as u64)
+      .size((HEADER_SIZE + payload.len()) as u64)
+      .build();
+
+    let mut datum = Vec::new();
+    encode_header(&mut datum, &header).unwrap();
+    datum.extend_from_slice(payload);
+
+    let mut decoder = Decoder::new(datum.as_slice());
+    let decoded_header = decoder.decode_header().unwrap();
+    assert_eq!(decoded_header.size, header.size);
+    assert!(decoder.is_streamable());
+
+    let mut collected = Vec::new();
+    let mut chunk = [0u8; 8];
+    loop {
+      let n = decoder.next_chunk(&mut chunk).unwrap();
+      if n == 0 {
+        break;
+      }
+      collected.extend_from_slice(&chunk[..n]);
+    }
+    assert_eq!(collected, payload);
+  }
+
+  #[test]
+  fn decoder_next_chunk_reports_truncated_payload() {
+    let header = HeaderBuilder::new()
+      .version(VERSION)
+      .size((HEADER_SIZE + 16) as u64)
+      .build();
+
+    let mut datum = Vec::new();
+    encode_header(&mut datum, &header).unwrap();
+    datum.extend_from_slice(&[0u8; 4]); // only 4 of the declared 16 payload bytes
+
+    let mut decoder = Decoder::new(datum.as_slice());
+    decoder.decode_header().unwrap();
+
+    let mut chunk = [0u8; 4];
+    let n = decoder.next_chunk(&mut chunk).unwrap();
+    assert_eq!(n, 4);
+
+    match decoder.next_chunk(&mut chunk) {
+      Err(ErrorType::Truncated(remaining)) => assert_eq!(remaining, 12),
+      other => panic!("expected a Truncated error, got {:?}", other),
+    }
+  }
 }